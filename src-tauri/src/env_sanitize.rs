@@ -0,0 +1,179 @@
+//! Strips credentials out of the environment before it reaches an agent
+//! shell. Defaults to a denylist of obviously-sensitive patterns, with an
+//! optional strict allowlist mode for users who want to start from nothing
+//! and only re-admit vetted variables. Rules are persisted through the
+//! [`settings`](crate::settings) subsystem.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::settings;
+
+/// Always kept in allowlist mode, regardless of the user's rules, so the
+/// shell and PTY stay usable.
+const REQUIRED_VARS: &[&str] = &["PATH", "HOME", "USERPROFILE", "TERM", "COLORTERM", "LANG", "SHELL"];
+
+fn default_denylist() -> Vec<String> {
+    vec![
+        "*_TOKEN".to_string(),
+        "*_SECRET".to_string(),
+        "*_KEY".to_string(),
+        "*_PASSWORD".to_string(),
+        "AWS_*".to_string(),
+        "GITHUB_TOKEN".to_string(),
+    ]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanitizationRules {
+    /// Glob-style patterns (`*` as wildcard) stripped from the environment.
+    #[serde(default = "default_denylist")]
+    pub denylist: Vec<String>,
+    /// When set, `denylist` is ignored and the environment is rebuilt from
+    /// nothing, keeping only `REQUIRED_VARS`, the harness's own `HARNESS_*`
+    /// variables, and anything matching `allowlist`.
+    #[serde(default)]
+    pub allowlist_mode: bool,
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
+impl Default for SanitizationRules {
+    fn default() -> Self {
+        Self {
+            denylist: default_denylist(),
+            allowlist_mode: false,
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+/// Matches `name` against a glob pattern that may contain at most one `*`.
+/// Case-insensitive: environment variable names aren't reliably uppercase
+/// on Windows. For the denylist this strips more, never less; for the
+/// allowlist it likewise admits a name whatever its case.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_ascii_uppercase();
+    let name = name.to_ascii_uppercase();
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => name.starts_with(prefix) && name.ends_with(suffix),
+        None => pattern == name,
+    }
+}
+
+pub fn load_rules(app: &AppHandle) -> SanitizationRules {
+    settings::load(app).env_rules
+}
+
+/// Apply `rules` to `env`, stripping or rebuilding it as configured.
+pub fn apply(rules: &SanitizationRules, env: HashMap<String, String>) -> HashMap<String, String> {
+    if rules.allowlist_mode {
+        env.into_iter()
+            .filter(|(key, _)| {
+                REQUIRED_VARS.iter().any(|required| required.eq_ignore_ascii_case(key))
+                    || key.starts_with("HARNESS_")
+                    || rules.allowlist.iter().any(|pattern| glob_match(pattern, key))
+            })
+            .collect()
+    } else {
+        env.into_iter()
+            .filter(|(key, _)| !rules.denylist.iter().any(|pattern| glob_match(pattern, key)))
+            .collect()
+    }
+}
+
+#[tauri::command]
+pub fn get_env_rules(app: AppHandle) -> SanitizationRules {
+    load_rules(&app)
+}
+
+#[tauri::command]
+pub fn set_env_rules(app: AppHandle, rules: SanitizationRules) -> Result<(), String> {
+    settings::update(&app, |settings| {
+        settings.env_rules = rules;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_exact_pattern() {
+        assert!(glob_match("GITHUB_TOKEN", "GITHUB_TOKEN"));
+        assert!(!glob_match("GITHUB_TOKEN", "GITHUB_TOKENS"));
+    }
+
+    #[test]
+    fn matches_case_insensitively() {
+        assert!(glob_match("GITHUB_TOKEN", "github_token"));
+        assert!(glob_match("aws_*", "AWS_SECRET_ACCESS_KEY"));
+    }
+
+    #[test]
+    fn matches_suffix_wildcard() {
+        assert!(glob_match("*_TOKEN", "GITHUB_TOKEN"));
+        assert!(glob_match("*_TOKEN", "_TOKEN"));
+        assert!(!glob_match("*_TOKEN", "TOKEN_GITHUB"));
+    }
+
+    #[test]
+    fn matches_prefix_wildcard() {
+        assert!(glob_match("AWS_*", "AWS_SECRET_ACCESS_KEY"));
+        assert!(glob_match("AWS_*", "AWS_"));
+        assert!(!glob_match("AWS_*", "MY_AWS_KEY"));
+    }
+
+    #[test]
+    fn wildcard_alone_matches_everything() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "ANYTHING"));
+    }
+
+    #[test]
+    fn only_the_first_wildcard_is_significant() {
+        // split_once only looks at the first `*`, so a second `*` is taken
+        // literally as part of the suffix rather than acting as a wildcard.
+        assert!(!glob_match("*_SECRET_*", "MY_SECRET_KEY"));
+        assert!(glob_match("*_SECRET_*", "MY_SECRET_*"));
+    }
+
+    #[test]
+    fn denylist_strips_matching_vars() {
+        let rules = SanitizationRules::default();
+        let env = HashMap::from([
+            ("GITHUB_TOKEN".to_string(), "secret".to_string()),
+            ("Aws_Secret_Access_Key".to_string(), "secret".to_string()),
+            ("PATH".to_string(), "/usr/bin".to_string()),
+        ]);
+
+        let sanitized = apply(&rules, env);
+
+        assert!(!sanitized.contains_key("GITHUB_TOKEN"));
+        assert!(!sanitized.contains_key("Aws_Secret_Access_Key"));
+        assert_eq!(sanitized.get("PATH"), Some(&"/usr/bin".to_string()));
+    }
+
+    #[test]
+    fn allowlist_mode_keeps_only_required_and_allowed_vars() {
+        let rules = SanitizationRules {
+            denylist: Vec::new(),
+            allowlist_mode: true,
+            allowlist: vec!["MY_APP_*".to_string()],
+        };
+        let env = HashMap::from([
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("MY_APP_CONFIG".to_string(), "value".to_string()),
+            ("GITHUB_TOKEN".to_string(), "secret".to_string()),
+        ]);
+
+        let sanitized = apply(&rules, env);
+
+        assert!(sanitized.contains_key("PATH"));
+        assert!(sanitized.contains_key("MY_APP_CONFIG"));
+        assert!(!sanitized.contains_key("GITHUB_TOKEN"));
+    }
+}
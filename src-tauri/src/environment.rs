@@ -0,0 +1,98 @@
+//! Builds the environment map handed to PTY-spawned shells and the `claude`
+//! CLI they launch, with per-platform defaults so the terminal behaves
+//! sensibly on POSIX and Windows alike.
+
+use std::collections::HashMap;
+
+#[cfg(windows)]
+const PATH_SEPARATOR: &str = ";";
+#[cfg(not(windows))]
+const PATH_SEPARATOR: &str = ":";
+
+#[cfg(windows)]
+fn user_bin_paths(env: &HashMap<String, String>) -> Vec<String> {
+    let Some(profile) = env.get("USERPROFILE").cloned() else {
+        return Vec::new();
+    };
+    vec![
+        format!("{}\\.local\\bin", profile),
+        format!("{}\\scoop\\shims", profile),
+        format!("{}\\.cargo\\bin", profile),
+    ]
+}
+
+#[cfg(not(windows))]
+fn user_bin_paths(env: &HashMap<String, String>) -> Vec<String> {
+    let Some(home) = env.get("HOME").cloned() else {
+        return Vec::new();
+    };
+    vec![format!("{}/.local/bin", home), format!("{}/bin", home)]
+}
+
+#[cfg(windows)]
+fn apply_platform_defaults(_env: &mut HashMap<String, String>) {
+    // TERM/LANG confuse cmd.exe and PowerShell rather than helping them, so
+    // leave them unset on Windows.
+}
+
+#[cfg(not(windows))]
+fn apply_platform_defaults(env: &mut HashMap<String, String>) {
+    // Ensure terminal-specific vars are set for full shell experience
+    env.entry("TERM".to_string())
+        .or_insert_with(|| "xterm-256color".to_string());
+    env.entry("COLORTERM".to_string())
+        .or_insert_with(|| "truecolor".to_string());
+
+    // Ensure locale is set for unicode support
+    if !env.contains_key("LANG") {
+        env.insert("LANG".to_string(), "en_US.UTF-8".to_string());
+    }
+}
+
+/// Best-effort Rust target triple for the running binary, derived from
+/// `std::env::consts` rather than whatever leaked in from the parent shell.
+fn target_triple() -> &'static str {
+    use std::env::consts::{ARCH, OS};
+    match (OS, ARCH) {
+        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+        ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+        ("windows", "aarch64") => "aarch64-pc-windows-msvc",
+        _ => "unknown",
+    }
+}
+
+/// Harness-provided build/target metadata, computed from the running
+/// binary's target rather than inherited from the parent shell, so agent
+/// CLIs and hook scripts can branch on the actual runtime platform.
+fn target_metadata() -> HashMap<String, String> {
+    use std::env::consts::{ARCH, FAMILY, OS};
+
+    let mut vars = HashMap::new();
+    vars.insert("HARNESS_TARGET_TRIPLE".to_string(), target_triple().to_string());
+    vars.insert("HARNESS_PLATFORM".to_string(), OS.to_string());
+    vars.insert("HARNESS_ARCH".to_string(), ARCH.to_string());
+    vars.insert("HARNESS_FAMILY".to_string(), FAMILY.to_string());
+    vars
+}
+
+/// Get the current process environment variables, with platform-appropriate
+/// defaults and user bin directories prepended to `PATH`.
+pub fn get_environment() -> HashMap<String, String> {
+    let mut env: HashMap<String, String> = std::env::vars().collect();
+
+    apply_platform_defaults(&mut env);
+
+    let user_paths = user_bin_paths(&env);
+    if !user_paths.is_empty() {
+        let current_path = env.get("PATH").cloned().unwrap_or_default();
+        let new_path = format!("{}{}{}", user_paths.join(PATH_SEPARATOR), PATH_SEPARATOR, current_path);
+        env.insert("PATH".to_string(), new_path);
+    }
+
+    env.extend(target_metadata());
+
+    env
+}
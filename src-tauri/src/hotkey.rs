@@ -0,0 +1,77 @@
+//! Global toggle-hotkey subsystem: lets the user summon/dismiss the main
+//! window from anywhere, quake-console style. The bound accelerator is
+//! persisted through the [`settings`](crate::settings) subsystem so it
+//! survives restarts.
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use crate::settings;
+
+fn toggle_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    let is_visible = window.is_visible().unwrap_or(false);
+    let is_focused = window.is_focused().unwrap_or(false);
+    if is_visible && is_focused {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Bind `accelerator`, only releasing `previous` once the new one is
+/// confirmed bound. `previous` is `None` for the very first bind at
+/// startup. Registering first (rather than unregistering then registering)
+/// means a rejected accelerator (invalid, or already claimed by the OS)
+/// leaves the previous binding intact instead of the app with nothing bound.
+pub fn rebind(app: &AppHandle, accelerator: &str, previous: Option<&str>) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    shortcuts.register(accelerator).map_err(|e| e.to_string())?;
+
+    if let Some(previous) = previous {
+        if previous != accelerator {
+            let _ = shortcuts.unregister(previous);
+        }
+    }
+
+    Ok(())
+}
+
+/// Load the persisted accelerator (or the default) and bind it. Called once
+/// during `setup()`. A bad persisted accelerator (unsupported combo on this
+/// OS, hand-edited settings file) must not block app launch, so failure here
+/// is logged and leaves the hotkey unregistered rather than propagated.
+pub fn init(app: &AppHandle) {
+    let accelerator = settings::load(app).toggle_hotkey;
+    if let Err(err) = rebind(app, &accelerator, None) {
+        eprintln!("failed to bind toggle hotkey {accelerator:?}: {err}");
+    }
+}
+
+pub fn plugin<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                toggle_main_window(app);
+            }
+        })
+        .build()
+}
+
+#[tauri::command]
+pub fn set_toggle_hotkey(app: AppHandle, accelerator: String) -> Result<(), String> {
+    settings::update(&app, |settings| {
+        rebind(&app, &accelerator, Some(&settings.toggle_hotkey))?;
+        settings.toggle_hotkey = accelerator;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn get_toggle_hotkey(app: AppHandle) -> String {
+    settings::load(&app).toggle_hotkey
+}
@@ -3,34 +3,19 @@ use tauri::Manager;
 
 use std::collections::HashMap;
 
-/// Get the current process environment variables
-#[tauri::command]
-fn get_environment() -> HashMap<String, String> {
-    let mut env: HashMap<String, String> = std::env::vars().collect();
-
-    // Ensure terminal-specific vars are set for full shell experience
-    env.entry("TERM".to_string())
-        .or_insert_with(|| "xterm-256color".to_string());
-    env.entry("COLORTERM".to_string())
-        .or_insert_with(|| "truecolor".to_string());
-
-    // Ensure locale is set for unicode support
-    if !env.contains_key("LANG") {
-        env.insert("LANG".to_string(), "en_US.UTF-8".to_string());
-    }
-
-    // Prepend common user bin paths to PATH (for claude CLI, etc.)
-    if let Some(home) = env.get("HOME").cloned() {
-        let user_paths = vec![
-            format!("{}/.local/bin", home),
-            format!("{}/bin", home),
-        ];
-        let current_path = env.get("PATH").cloned().unwrap_or_default();
-        let new_path = format!("{}:{}", user_paths.join(":"), current_path);
-        env.insert("PATH".to_string(), new_path);
-    }
+mod env_sanitize;
+mod environment;
+mod hotkey;
+mod profiles;
+mod settings;
 
-    env
+/// Get the current process environment variables, sanitized per the user's
+/// denylist/allowlist rules before it reaches an agent shell.
+#[tauri::command]
+fn get_environment(app: tauri::AppHandle) -> HashMap<String, String> {
+    let env = environment::get_environment();
+    let rules = env_sanitize::load_rules(&app);
+    env_sanitize::apply(&rules, env)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -39,13 +24,30 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_pty::init())
         .plugin(tauri_plugin_fs::init())
-        .invoke_handler(tauri::generate_handler![get_environment])
+        .plugin(hotkey::plugin())
+        .invoke_handler(tauri::generate_handler![
+            get_environment,
+            hotkey::set_toggle_hotkey,
+            hotkey::get_toggle_hotkey,
+            env_sanitize::get_env_rules,
+            env_sanitize::set_env_rules,
+            settings::get_settings,
+            settings::set_setting,
+            settings::reset_settings,
+            profiles::list_profiles,
+            profiles::create_profile,
+            profiles::resolve_profile_env
+        ])
         .setup(|_app| {
             #[cfg(debug_assertions)]
             {
                 let window = _app.get_webview_window("main").unwrap();
                 window.open_devtools();
             }
+
+            settings::init(_app.handle()).map_err(|e| -> Box<dyn std::error::Error> { e.into() })?;
+            hotkey::init(_app.handle());
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,56 @@
+//! Named shell profiles, so a terminal can be opened with e.g. "bash +
+//! claude PATH" or "login zsh" instead of every session inheriting one
+//! global environment. Profiles are persisted through the
+//! [`settings`](crate::settings) subsystem.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::{env_sanitize, environment, settings};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub shell: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Overlaid on top of the base environment before sanitization; values
+    /// here win unless the sanitization rules strip them.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> Vec<Profile> {
+    settings::load(&app).profiles
+}
+
+#[tauri::command]
+pub fn create_profile(app: AppHandle, profile: Profile) -> Result<(), String> {
+    settings::update(&app, |settings| {
+        settings.profiles.retain(|p| p.name != profile.name);
+        settings.profiles.push(profile);
+        Ok(())
+    })
+}
+
+/// The environment a PTY session opened under `name` should use: the base
+/// environment with the profile's overrides layered on top, then sanitized
+/// as a whole so a profile can't smuggle a denylisted var back in.
+#[tauri::command]
+pub fn resolve_profile_env(app: AppHandle, name: String) -> Result<HashMap<String, String>, String> {
+    let settings = settings::load(&app);
+    let profile = settings
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("no such profile: {name}"))?;
+
+    let mut env = environment::get_environment();
+    env.extend(profile.env.clone());
+    Ok(env_sanitize::apply(&settings.env_rules, env))
+}
@@ -0,0 +1,214 @@
+//! Persistent app settings: a single typed surface for preferences that
+//! previously had to be recomputed on every launch (toggle hotkey, env
+//! sanitization rules, default shell/cwd), backed by a JSON file in the
+//! app data dir.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::env_sanitize::SanitizationRules;
+use crate::profiles::Profile;
+
+const SETTINGS_FILE: &str = "settings.json";
+// Standalone hotkey and sanitization-rules files, if present in the app data
+// dir at startup. Folded into `Settings` by `migrate_legacy()`, then deleted.
+const LEGACY_HOTKEY_FILE: &str = "hotkey.json";
+const LEGACY_ENV_RULES_FILE: &str = "env_rules.json";
+
+fn default_shell() -> String {
+    #[cfg(windows)]
+    {
+        "powershell.exe".to_string()
+    }
+    #[cfg(not(windows))]
+    {
+        std::env::var("SHELL").unwrap_or_else(|_| "/bin/bash".to_string())
+    }
+}
+
+fn default_toggle_hotkey() -> String {
+    "CmdOrCtrl+Shift+Space".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default = "default_shell")]
+    pub default_shell: String,
+    #[serde(default)]
+    pub default_shell_args: Vec<String>,
+    #[serde(default = "default_toggle_hotkey")]
+    pub toggle_hotkey: String,
+    #[serde(default)]
+    pub env_rules: SanitizationRules,
+    #[serde(default)]
+    pub default_cwd: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            default_shell: default_shell(),
+            default_shell_args: Vec::new(),
+            toggle_hotkey: default_toggle_hotkey(),
+            env_rules: SanitizationRules::default(),
+            default_cwd: None,
+            profiles: Vec::new(),
+        }
+    }
+}
+
+fn settings_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+fn settings_file_path(app: &AppHandle) -> Result<PathBuf, String> {
+    settings_dir(app).map(|dir| dir.join(SETTINGS_FILE))
+}
+
+/// Fold any pre-settings-subsystem `hotkey.json` / `env_rules.json` into
+/// `Settings` and delete them, so a user who customized either before
+/// upgrading doesn't silently lose that customization.
+fn migrate_legacy(app: &AppHandle) -> Result<(), String> {
+    let dir = settings_dir(app)?;
+    let hotkey_path = dir.join(LEGACY_HOTKEY_FILE);
+    let env_rules_path = dir.join(LEGACY_ENV_RULES_FILE);
+
+    if !hotkey_path.exists() && !env_rules_path.exists() {
+        return Ok(());
+    }
+
+    let mut settings = load(app);
+
+    if let Ok(contents) = fs::read_to_string(&hotkey_path) {
+        if let Ok(accelerator) = serde_json::from_str::<String>(&contents) {
+            settings.toggle_hotkey = accelerator;
+        }
+        let _ = fs::remove_file(&hotkey_path);
+    }
+
+    if let Ok(contents) = fs::read_to_string(&env_rules_path) {
+        if let Ok(rules) = serde_json::from_str::<SanitizationRules>(&contents) {
+            settings.env_rules = rules;
+        }
+        let _ = fs::remove_file(&env_rules_path);
+    }
+
+    save(app, &settings)
+}
+
+/// Create the config directory if it doesn't exist yet and migrate any
+/// legacy per-feature settings files. Called from `setup()`.
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    fs::create_dir_all(settings_dir(app)?).map_err(|e| e.to_string())?;
+    migrate_legacy(app)
+}
+
+fn lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// Acquires the settings mutex, recovering rather than failing the caller if
+/// a prior holder panicked while holding it.
+fn acquire() -> std::sync::MutexGuard<'static, ()> {
+    lock().lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn read_file(app: &AppHandle) -> Settings {
+    settings_file_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn write_file(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = settings_file_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+
+    // Write to a temp file and rename into place so a reader never observes
+    // a truncated/partial settings.json.
+    let tmp_path = path.with_file_name(format!("{SETTINGS_FILE}.tmp"));
+    fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, &path).map_err(|e| e.to_string())
+}
+
+/// Reads `settings.json` under the same lock `update()` writes under, so a
+/// read can't observe a write that's still in progress.
+pub fn load(app: &AppHandle) -> Settings {
+    let _guard = acquire();
+    read_file(app)
+}
+
+/// Serializes a read-modify-write cycle against `settings.json` so two
+/// concurrent command invocations (e.g. `create_profile` racing
+/// `set_setting`) can't clobber each other's update.
+pub fn update<F>(app: &AppHandle, f: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Settings) -> Result<(), String>,
+{
+    let _guard = acquire();
+    let mut settings = read_file(app);
+    f(&mut settings)?;
+    write_file(app, &settings)
+}
+
+/// Writes `settings` to disk directly, without taking the settings lock.
+/// Only safe to call before the app's command handlers are reachable (e.g.
+/// from `init()`/`migrate_legacy()` during `setup()`).
+fn save(app: &AppHandle, settings: &Settings) -> Result<(), String> {
+    write_file(app, settings)
+}
+
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> Settings {
+    load(&app)
+}
+
+#[tauri::command]
+pub fn set_setting(app: AppHandle, key: String, value: Value) -> Result<(), String> {
+    update(&app, |settings| {
+        match key.as_str() {
+            "default_shell" => {
+                settings.default_shell = serde_json::from_value(value).map_err(|e| e.to_string())?
+            }
+            "default_shell_args" => {
+                settings.default_shell_args =
+                    serde_json::from_value(value).map_err(|e| e.to_string())?
+            }
+            "toggle_hotkey" => {
+                let accelerator: String =
+                    serde_json::from_value(value).map_err(|e| e.to_string())?;
+                crate::hotkey::rebind(&app, &accelerator, Some(&settings.toggle_hotkey))?;
+                settings.toggle_hotkey = accelerator;
+            }
+            "env_rules" => {
+                settings.env_rules = serde_json::from_value(value).map_err(|e| e.to_string())?
+            }
+            "default_cwd" => {
+                settings.default_cwd = serde_json::from_value(value).map_err(|e| e.to_string())?
+            }
+            other => return Err(format!("unknown setting: {other}")),
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn reset_settings(app: AppHandle) -> Result<(), String> {
+    update(&app, |settings| {
+        let previous = settings.toggle_hotkey.clone();
+        *settings = Settings::default();
+        crate::hotkey::rebind(&app, &settings.toggle_hotkey, Some(&previous))
+    })
+}